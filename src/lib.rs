@@ -39,6 +39,15 @@ where
     size_x: u16,
     size_y: u16,
 
+    // Panel RAM offset, as seen by CASET/RASET, to account for panel variants
+    // whose visible area doesn't start at RAM origin (0, 0)
+    offset_x: u16,
+    offset_y: u16,
+
+    // Active display orientation, so offset_x/offset_y and width()/height()
+    // can be kept consistent with the rotated panel
+    orientation: Orientation,
+
     // Delay provider
     delay: DELAY,
 }
@@ -46,7 +55,7 @@ where
 ///
 /// Display orientation.
 ///
-#[derive(ToPrimitive)]
+#[derive(Clone, Copy, PartialEq, ToPrimitive)]
 pub enum Orientation {
     Portrait = 0b0000_0000,         // no inverting
     Landscape = 0b0110_0000,        // invert column and page/column order
@@ -63,6 +72,39 @@ pub enum Error<RSTE> {
     Rst(RSTE),
 }
 
+///
+/// Optional tuning registers for `init_with_config`, covering frame-rate,
+/// power, and gamma correction. Fields left as `None` are skipped, leaving
+/// the display's own default for that register in place.
+///
+#[derive(Default)]
+pub struct Config {
+    /// FRMCTR1: frame rate control in normal mode (3 bytes)
+    pub frmctr1: Option<[u8; 3]>,
+    /// FRMCTR2: frame rate control in idle mode (3 bytes)
+    pub frmctr2: Option<[u8; 3]>,
+    /// FRMCTR3: frame rate control in partial mode (6 bytes)
+    pub frmctr3: Option<[u8; 6]>,
+    /// PWCTR1: power control 1 (3 bytes)
+    pub pwctr1: Option<[u8; 3]>,
+    /// PWCTR2: power control 2 (1 byte)
+    pub pwctr2: Option<[u8; 1]>,
+    /// PWCTR3: power control 3 (2 bytes)
+    pub pwctr3: Option<[u8; 2]>,
+    /// PWCTR4: power control 4 (2 bytes)
+    pub pwctr4: Option<[u8; 2]>,
+    /// PWCTR5: power control 5 (2 bytes)
+    pub pwctr5: Option<[u8; 2]>,
+    /// PWCTR6: power control 6 (1 byte)
+    pub pwctr6: Option<[u8; 1]>,
+    /// VMCTR1: VCOM control 1 (1 byte)
+    pub vmctr1: Option<[u8; 1]>,
+    /// GMCTRP1: positive gamma correction (16 bytes)
+    pub gamma_pos: Option<[u8; 16]>,
+    /// GMCTRN1: negative gamma correction (16 bytes)
+    pub gamma_neg: Option<[u8; 16]>,
+}
+
 impl<DI, RST, DELAY> ST7789<DI, RST, DELAY>
 where
     DI: WriteOnlyDataCommand<u8>,
@@ -82,19 +124,88 @@ where
     /// * `delay` - delay provider, required for proper RST and DC timings
     ///
     pub fn new(di: DI, rst: RST, size_x: u16, size_y: u16, delay: DELAY) -> Self {
+        Self::new_with_offset(di, rst, size_x, size_y, delay, 0, 0)
+    }
+
+    ///
+    /// Creates a new ST7789 driver instance with a fixed RAM offset, for
+    /// panel variants whose visible area doesn't start at RAM origin (0, 0),
+    /// e.g. many 240x240 round or 135x240 modules.
+    ///
+    /// # Arguments
+    ///
+    /// * `di` - a display interface to use for talking to the display
+    /// * `rst` - display hard reset pin
+    /// * `size_x` - x axis resolution of the display in pixels
+    /// * `size_y` - y axis resolution of the display in pixels
+    /// * `delay` - delay provider, required for proper RST and DC timings
+    /// * `offset_x` - x axis RAM offset, in pixels, for the panel's portrait orientation
+    /// * `offset_y` - y axis RAM offset, in pixels, for the panel's portrait orientation
+    ///
+    pub fn new_with_offset(
+        di: DI,
+        rst: RST,
+        size_x: u16,
+        size_y: u16,
+        delay: DELAY,
+        offset_x: u16,
+        offset_y: u16,
+    ) -> Self {
         ST7789 {
             di,
             rst,
             size_x,
             size_y,
+            offset_x,
+            offset_y,
+            orientation: Orientation::Portrait,
             delay,
         }
     }
 
+    ///
+    /// Returns the display's currently active orientation.
+    ///
+    pub fn orientation(&self) -> Orientation {
+        self.orientation
+    }
+
+    ///
+    /// Returns the effective drawable width in pixels, accounting for the
+    /// portrait/landscape swap from the active orientation.
+    ///
+    pub fn width(&self) -> u16 {
+        match self.orientation {
+            Orientation::Landscape | Orientation::LandscapeSwapped => self.size_y,
+            Orientation::Portrait | Orientation::PortraitSwapped => self.size_x,
+        }
+    }
+
+    ///
+    /// Returns the effective drawable height in pixels, accounting for the
+    /// portrait/landscape swap from the active orientation.
+    ///
+    pub fn height(&self) -> u16 {
+        match self.orientation {
+            Orientation::Landscape | Orientation::LandscapeSwapped => self.size_x,
+            Orientation::Portrait | Orientation::PortraitSwapped => self.size_y,
+        }
+    }
+
     ///
     /// Runs commands to initialize the display
     ///
     pub fn init(&mut self) -> Result<(), Error<RST::Error>> {
+        self.init_with_config(&Config::default())
+    }
+
+    ///
+    /// Runs commands to initialize the display, additionally writing any
+    /// tuning registers set in `cfg`. This lets panels that ghost, flicker,
+    /// or have inaccurate colors with the default sequence be corrected
+    /// without forking the crate.
+    ///
+    pub fn init_with_config(&mut self, cfg: &Config) -> Result<(), Error<RST::Error>> {
         self.hard_reset()?;
         self.write_command(Instruction::SWRESET, None)?; // reset display
         self.delay.delay_us(150_000);
@@ -102,7 +213,46 @@ where
         self.delay.delay_us(10_000);
         self.write_command(Instruction::INVOFF, None)?; // turn off invert
         self.write_command(Instruction::MADCTL, Some(&[0b0000_0000]))?; // left -> right, bottom -> top RGB
+        self.track_orientation(Orientation::Portrait);
         self.write_command(Instruction::COLMOD, Some(&[0b0101_0101]))?; // 16bit 65k colors
+
+        if let Some(bytes) = &cfg.frmctr1 {
+            self.write_command(Instruction::FRMCTR1, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.frmctr2 {
+            self.write_command(Instruction::FRMCTR2, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.frmctr3 {
+            self.write_command(Instruction::FRMCTR3, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.pwctr1 {
+            self.write_command(Instruction::PWCTR1, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.pwctr2 {
+            self.write_command(Instruction::PWCTR2, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.pwctr3 {
+            self.write_command(Instruction::PWCTR3, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.pwctr4 {
+            self.write_command(Instruction::PWCTR4, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.pwctr5 {
+            self.write_command(Instruction::PWCTR5, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.pwctr6 {
+            self.write_command(Instruction::PWCTR6, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.vmctr1 {
+            self.write_command(Instruction::VMCTR1, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.gamma_pos {
+            self.write_command(Instruction::GMCTRP1, Some(&bytes[..]))?;
+        }
+        if let Some(bytes) = &cfg.gamma_neg {
+            self.write_command(Instruction::GMCTRN1, Some(&bytes[..]))?;
+        }
+
         self.write_command(Instruction::INVON, None)?; // hack?
         self.delay.delay_us(10_000);
         self.write_command(Instruction::NORON, None)?; // turn on display
@@ -126,14 +276,123 @@ where
         Ok(())
     }
 
+    ///
+    /// Puts the display to sleep, reducing power consumption. The display
+    /// retains its RAM contents but stops refreshing; call `wake` to resume.
+    ///
+    pub fn sleep(&mut self) -> Result<(), Error<RST::Error>> {
+        self.write_command(Instruction::SLPIN, None)
+    }
+
+    ///
+    /// Wakes the display up from sleep mode. Waits the mandatory 120ms for
+    /// the panel's power supply and oscillator to stabilize before returning.
+    ///
+    pub fn wake(&mut self) -> Result<(), Error<RST::Error>> {
+        self.write_command(Instruction::SLPOUT, None)?;
+        self.delay.delay_us(120_000);
+        Ok(())
+    }
+
+    ///
+    /// Enables or disables idle mode, which reduces the display to a lower
+    /// color depth (8 colors) to save power.
+    ///
+    pub fn set_idle_mode(&mut self, on: bool) -> Result<(), Error<RST::Error>> {
+        if on {
+            self.write_command(Instruction::IDMON, None)
+        } else {
+            self.write_command(Instruction::IDMOFF, None)
+        }
+    }
+
+    ///
+    /// Turns the display output on or off, without affecting sleep mode or
+    /// RAM contents.
+    ///
+    pub fn set_display_on(&mut self, on: bool) -> Result<(), Error<RST::Error>> {
+        if on {
+            self.write_command(Instruction::DISPON, None)
+        } else {
+            self.write_command(Instruction::DISPOFF, None)
+        }
+    }
+
+    ///
+    /// Sets the panel's content/backlight brightness via the controller's
+    /// own PWM brightness register, rather than an external GPIO. Only
+    /// works on panels whose backlight driver is wired to the controller's
+    /// LEDPWM pin.
+    ///
+    /// # Arguments
+    ///
+    /// * `level` - brightness level, from 0 (off) to 255 (full brightness)
+    ///
+    pub fn set_brightness(&mut self, level: u8) -> Result<(), Error<RST::Error>> {
+        // BCTRL (brightness control block on) + DD (display dimming on) + BL (backlight on)
+        self.write_command(Instruction::WRCTRLD, Some(&[0b0010_1100]))?;
+        self.write_command(Instruction::WRDISBV, Some(&[level]))
+    }
+
     ///
     /// Sets display orientation
     ///
     pub fn set_orientation(&mut self, orientation: &Orientation) -> Result<(), Error<RST::Error>> {
         self.write_command(Instruction::MADCTL, Some(&[orientation.to_u8().unwrap()]))?;
+        self.track_orientation(*orientation);
         Ok(())
     }
 
+    // Updates the tracked orientation, swapping offset_x/offset_y if the new
+    // orientation's portrait/landscape-ness differs from the current one, so
+    // offsets stay correct no matter how many times this is called in a row
+    // (e.g. re-init after a prior rotation).
+    fn track_orientation(&mut self, orientation: Orientation) {
+        let is_landscape = |o: &Orientation| {
+            matches!(o, Orientation::Landscape | Orientation::LandscapeSwapped)
+        };
+        if is_landscape(&orientation) != is_landscape(&self.orientation) {
+            core::mem::swap(&mut self.offset_x, &mut self.offset_y);
+        }
+        self.orientation = orientation;
+    }
+
+    ///
+    /// Sets the hardware vertical scrolling region (VSCRDEF).
+    ///
+    /// # Arguments
+    ///
+    /// * `tfa` - top fixed area, in lines
+    /// * `vsa` - vertical scrolling area, in lines
+    /// * `bfa` - bottom fixed area, in lines
+    ///
+    /// `tfa + vsa + bfa` must sum to the panel's total number of lines (320).
+    ///
+    pub fn set_scroll_region(
+        &mut self,
+        tfa: u16,
+        vsa: u16,
+        bfa: u16,
+    ) -> Result<(), Error<RST::Error>> {
+        self.write_command(Instruction::VSCRDEF, None)?;
+        self.write_word(tfa)?;
+        self.write_word(vsa)?;
+        self.write_word(bfa)
+    }
+
+    ///
+    /// Sets the vertical scroll start address (VSCSAD), i.e. which RAM line
+    /// is displayed at the top of the scrolling area.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset` - RAM line to scroll to
+    ///
+    pub fn set_scroll_offset(&mut self, offset: u16) -> Result<(), Error<RST::Error>> {
+        self.write_command(Instruction::VSCSAD, None)?;
+        self.write_word(offset)
+    }
+
     ///
     /// Sets a pixel color at the given coords.
     ///
@@ -176,6 +435,71 @@ where
         self.write_pixels(colors)
     }
 
+    ///
+    /// Fills a rectangle with a single color. With the `buffer` feature the
+    /// color is encoded once and streamed in 128-byte chunks; without it the
+    /// color word is written once per pixel.
+    ///
+    /// # Arguments
+    ///
+    /// * `sx` - x coordinate start
+    /// * `sy` - y coordinate start
+    /// * `ex` - x coordinate end
+    /// * `ey` - y coordinate end
+    /// * `color` - the Rgb565 color value
+    ///
+    pub fn fill_rect(
+        &mut self,
+        sx: u16,
+        sy: u16,
+        ex: u16,
+        ey: u16,
+        color: u16,
+    ) -> Result<(), Error<RST::Error>> {
+        self.set_address_window(sx, sy, ex, ey)?;
+        self.write_command(Instruction::RAMWR, None)?;
+        let count = (ex - sx + 1) as usize * (ey - sy + 1) as usize;
+        self.write_color(color, count)
+    }
+
+    ///
+    /// Fills the whole screen with a single color.
+    ///
+    /// # Arguments
+    ///
+    /// * `color` - the Rgb565 color value
+    ///
+    pub fn clear(&mut self, color: u16) -> Result<(), Error<RST::Error>> {
+        self.fill_rect(0, 0, self.width() - 1, self.height() - 1, color)
+    }
+
+    #[cfg(not(feature = "buffer"))]
+    fn write_color(&mut self, color: u16, count: usize) -> Result<(), Error<RST::Error>> {
+        for _ in 0..count {
+            self.write_word(color)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "buffer")]
+    fn write_color(&mut self, color: u16, count: usize) -> Result<(), Error<RST::Error>> {
+        let word = color.to_be_bytes();
+        let mut buf = [0; 128];
+        for chunk in buf.chunks_exact_mut(2) {
+            chunk.copy_from_slice(&word);
+        }
+
+        let mut remaining = count * 2;
+        while remaining > 0 {
+            let n = remaining.min(buf.len());
+            self.write_data(&buf[..n])?;
+            remaining -= n;
+        }
+
+        Ok(())
+    }
+
     #[cfg(not(feature = "buffer"))]
     fn write_pixels<T>(&mut self, colors: T) -> Result<(), Error<RST::Error>>
     where
@@ -249,10 +573,10 @@ where
         ey: u16,
     ) -> Result<(), Error<RST::Error>> {
         self.write_command(Instruction::CASET, None)?;
-        self.write_word(sx)?;
-        self.write_word(ex)?;
+        self.write_word(sx + self.offset_x)?;
+        self.write_word(ex + self.offset_x)?;
         self.write_command(Instruction::RASET, None)?;
-        self.write_word(sy)?;
-        self.write_word(ey)
+        self.write_word(sy + self.offset_y)?;
+        self.write_word(ey + self.offset_y)
     }
 }